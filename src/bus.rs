@@ -1,3 +1,5 @@
+use crate::savestate::Savable;
+
 /// The Bus trait defines how the CPU interacts with memory or devices.
 /// It requires two functions:
 /// - `read`: read a byte (u8) from a 16-bit address (u16)
@@ -13,6 +15,12 @@ pub struct Ram {
     mem: [u8; 65536],  // Memory array: each element is one byte
 }
 
+impl Default for Ram {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Ram {
     /// Creates a new Ram instance with all bytes initialized to zero.
     pub fn new() -> Self {
@@ -45,3 +53,40 @@ impl Bus for Ram {
         self.mem[addr as usize] = data;
     }
 }
+
+impl Savable for Ram {
+    /// Run-length encodes `mem` as `(run_len: u32 LE, value: u8)` pairs,
+    /// which compacts well since most of memory is zero at startup.
+    fn save_state(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        let mut i = 0usize;
+
+        while i < self.mem.len() {
+            let value = self.mem[i];
+            let start = i;
+            while i < self.mem.len() && self.mem[i] == value {
+                i += 1;
+            }
+
+            let run_len = (i - start) as u32;
+            out.extend_from_slice(&run_len.to_le_bytes());
+            out.push(value);
+        }
+
+        out
+    }
+
+    /// Restores `mem` from a buffer produced by `save_state`.
+    fn load_state(&mut self, data: &[u8]) {
+        let mut pos = 0;
+        let mut offset = 0usize;
+
+        while pos < data.len() {
+            let run_len = u32::from_le_bytes(data[pos..pos + 4].try_into().unwrap()) as usize;
+            let value = data[pos + 4];
+            self.mem[offset..offset + run_len].fill(value);
+            offset += run_len;
+            pos += 5;
+        }
+    }
+}
@@ -1,25 +1,43 @@
+use std::marker::PhantomData;
+
 use crate::bus::Bus;
+use crate::opcodes::{AddressingMode, Instruction};
+use crate::savestate::Savable;
+use crate::variant::{Nmos, Variant};
 
 const NMI_VECTOR: u16 = 0xFFFA;
 const RESET_VECTOR: u16 = 0xFFFC;
 const IRQ_VECTOR: u16 = 0xFFFE;
 
+/// Cycles an interrupt dispatch takes: pushing PC and status plus
+/// fetching the two vector bytes, same as BRK's sequence.
+const INTERRUPT_DISPATCH_CYCLES: u8 = 7;
+
 /// The CPU struct represents the central processing unit.
 /// It holds registers and status flags required for execution.
-pub struct CPU {
+///
+/// `CPU` is generic over a `Variant` (defaulting to `Nmos`) so the same
+/// core emulates either the NMOS 6502 or the CMOS 65C02 depending on
+/// which opcode table and behavioral quirks `V` selects.
+pub struct CPU<V: Variant = Nmos> {
     /// Accumulator register (A), used for arithmetic and logic operations.
     pub register_a: u8,
 
     /// Index register X, used for indexing and loop counters.
     pub register_x: u8,
 
+    /// Index register Y, used for indexing and loop counters.
+    pub register_y: u8,
+
     /// Status register holding CPU flags:
     /// - Bit 7: Negative flag (N)
+    /// - Bit 6: Overflow flag (V)
     /// - Bit 1: Zero flag (Z)
     /// - Bit 0: Carry flag (C)
+    ///
     /// and others (not fully implemented here).
     pub status: u8,
- 
+
     /// Program counter (PC), points to the next instruction address.
     pub program_counter: u16,
 
@@ -30,20 +48,69 @@ pub struct CPU {
     /// Pending interrupt requests (NMI cannot be masked, IRQ can be).
     pub nmi_pending: bool,
     pub irq_pending: bool,
+
+    /// Total number of cycles executed since this CPU was created, used
+    /// to pace execution against a PPU/APU driven off the same clock.
+    pub cycles: u64,
+
+    _variant: PhantomData<V>,
 }
 
-impl CPU { 
-    /// Creates a new CPU instance with all registers and flags initialized to zero.
+impl CPU<Nmos> {
+    /// Creates a new NMOS CPU instance with all registers and flags
+    /// initialized to zero, except the stack pointer, which starts at
+    /// 0xFD. The struct's default type parameter only
+    /// applies when `CPU`'s type is written out explicitly (e.g. `let x:
+    /// CPU = ...`), not for a bare `CPU::new()` whose return type is
+    /// inferred -- so this inherent method, rather than the default
+    /// parameter alone, is what makes the common NMOS case infer without
+    /// annotation. For the 65C02, use `CPU::<Cmos>::new_variant()`.
     pub fn new() -> Self {
+        Self::new_variant()
+    }
+}
+
+impl Default for CPU<Nmos> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V: Variant> CPU<V> {
+    /// Creates a new CPU instance for variant `V`, with all registers and
+    /// flags initialized to zero except the stack pointer (0xFD). Prefer `CPU::new()` for the common NMOS
+    /// case; this entry point exists for callers that need to name `V`
+    /// explicitly, e.g. `CPU::<Cmos>::new_variant()`.
+    pub fn new_variant() -> Self {
         CPU {
             register_a: 0,
             register_x: 0,
+            register_y: 0,
             status: 0,
             program_counter: 0,
             stack_pointer: 0xFD,
+            nmi_pending: false,
+            irq_pending: false,
+            cycles: 0,
+            _variant: PhantomData,
         }
     }
-    
+
+    /// Performs the power-on/reset sequence: sets the stack pointer to
+    /// 0xFD, the status register to the power-on value 0x24
+    /// (Interrupt-Disable set, plus the unused bit), and loads
+    /// `program_counter` from the reset vector at 0xFFFC/0xFFFD. Callers
+    /// should place their entry point there rather than assuming
+    /// execution starts at address 0.
+    pub fn reset(&mut self, bus: &mut impl Bus) {
+        self.stack_pointer = 0xFD;
+        self.status = 0x24;
+
+        let lo = bus.read(RESET_VECTOR) as u16;
+        let hi = bus.read(RESET_VECTOR + 1) as u16;
+        self.program_counter = (hi << 8) | lo;
+    }
+
     /// Requests a maskable interrupt (IRQ). Ignored if I flag is set.
     pub fn trigger_irq(&mut self) {
         self.irq_pending = true;
@@ -56,8 +123,8 @@ impl CPU {
         let pc = self.program_counter;
         self.push_word(bus, pc);
 
-        // Push status (B flag cleared on actual interrupts) 
-        let flags = self.status & 0b1110_1111; // Clear B flag
+        // Push status (B flag cleared, unused bit 5 set, as on real hardware)
+        let flags = (self.status & 0b1110_1111) | 0b0010_0000;
         self.push_byte(bus, flags);
 
         // Set interrupt disable flag
@@ -77,9 +144,9 @@ impl CPU {
     /// Computes the absolute memory address of the stack location pointed by 'stack_pointer'.
     /// Stack resides in page 0x0100 (0x0100 - 0x01FF).
     fn stack_address(&self) -> u16 {
-        0x0100 | self.stack_pointer as u16 
+        0x0100 | self.stack_pointer as u16
     }
-    
+
     /// Pushes a byte onto the stack.
     /// Decrements `stack_pointer` after writing (stack grows downward).
     pub fn push_byte(&mut self, bus: &mut impl Bus, value: u8){
@@ -120,191 +187,542 @@ impl CPU {
     /// - Negative flag is set if the most significant bit (bit 7) is set.
     fn update_zero_and_negative_flags(&mut self, result: u8) {
         if result == 0 {
-            self.status = self.status | 0b0000_0010; // Set zero flag
+            self.status |= 0b0000_0010; // Set zero flag
         } else {
-            self.status = self.status & 0b1111_1101; // Clear zero flag
+            self.status &= 0b1111_1101; // Clear zero flag
         }
 
         if result & 0b1000_0000 != 0 {
-            self.status = self.status | 0b1000_0000; // Set negative flag
+            self.status |= 0b1000_0000; // Set negative flag
         } else {
-            self.status = self.status & 0b0111_1111; // Clear negative flag
+            self.status &= 0b0111_1111; // Clear negative flag
         }
     }
-    
-    /// Adjusts the program counter by a signed offset for branching instructions.
-    fn branch(&mut self, offset: i8) {
-        let pc = self.program_counter as i32;
-        let offset = offset as i32;  
-        self.program_counter = (pc + offset) as u16;
+
+    /// Adds `operand` plus the current Carry flag into `register_a`, the
+    /// shared path for both ADC (operand as-is) and SBC (operand inverted).
+    /// Sets Carry on unsigned overflow past 0xFF and Overflow when the two
+    /// inputs share a sign that differs from the result's sign.
+    fn add_with_carry(&mut self, operand: u8) {
+        let carry_in = (self.status & 0b0000_0001) as u16;
+        let a = self.register_a;
+        let sum = a as u16 + operand as u16 + carry_in;
+        let result = sum as u8;
+
+        if sum > 0xFF {
+            self.status |= 0b0000_0001; // Set carry flag
+        } else {
+            self.status &= 0b1111_1110; // Clear carry flag
+        }
+
+        if (a ^ result) & (operand ^ result) & 0b1000_0000 != 0 {
+            self.status |= 0b0100_0000; // Set overflow flag
+        } else {
+            self.status &= 0b1011_1111; // Clear overflow flag
+        }
+
+        self.register_a = result;
+        self.update_zero_and_negative_flags(self.register_a);
     }
-  
-    /// Runs the CPU emulation loop, fetching and executing instructions from the bus.
-    /// The loop continues until a BRK (0x00) instruction is encountered.
+
+    /// ADC: adds `operand` into `register_a`. N, V, and Z always come from
+    /// the binary result computed by `add_with_carry`; with the
+    /// `decimal_mode` feature enabled and the Decimal flag set, `register_a`
+    /// and Carry are then overwritten with the BCD-adjusted result, matching
+    /// NMOS hardware's quirk of deriving flags from the pre-adjustment value.
+    fn adc(&mut self, operand: u8) {
+        #[cfg(feature = "decimal_mode")]
+        let (a, carry_in) = (self.register_a, self.status & 0b0000_0001);
+
+        self.add_with_carry(operand);
+
+        #[cfg(feature = "decimal_mode")]
+        if self.status & 0b0000_1000 != 0 {
+            self.register_a = self.decimal_add(a, operand, carry_in);
+        }
+    }
+
+    /// SBC: subtracts `operand` (plus borrow) from `register_a`, reusing
+    /// `add_with_carry`'s two's-complement trick (`!operand`) for the binary
+    /// flags. With `decimal_mode` enabled and the Decimal flag set,
+    /// `register_a` and Carry are then overwritten with the BCD-adjusted
+    /// result, the same NMOS quirk `adc` preserves.
+    fn sbc(&mut self, operand: u8) {
+        #[cfg(feature = "decimal_mode")]
+        let (a, carry_in) = (self.register_a, self.status & 0b0000_0001);
+
+        self.add_with_carry(!operand);
+
+        #[cfg(feature = "decimal_mode")]
+        if self.status & 0b0000_1000 != 0 {
+            self.register_a = self.decimal_subtract(a, operand, carry_in);
+        }
+    }
+
+    /// Nibble-wise BCD addition of `a + operand + carry_in`, per the NMOS
+    /// decimal-mode algorithm: add each nibble separately, and whenever a
+    /// nibble's sum exceeds 9, add 6 to pull it back into valid-digit range
+    /// and carry the overflow into the next nibble up. Sets the Carry flag
+    /// (only) here; N/V/Z were already set from the binary result by `adc`.
+    #[cfg(feature = "decimal_mode")]
+    fn decimal_add(&mut self, a: u8, operand: u8, carry_in: u8) -> u8 {
+        let mut lo = (a & 0x0F) as u16 + (operand & 0x0F) as u16 + carry_in as u16;
+        if lo > 9 {
+            lo += 6;
+        }
+        let carry_to_hi = if lo > 0x0F { 1 } else { 0 };
+        lo &= 0x0F;
+
+        let mut hi = (a >> 4) as u16 + (operand >> 4) as u16 + carry_to_hi;
+        if hi > 9 {
+            hi += 6;
+        }
+
+        if hi > 0x0F {
+            self.status |= 0b0000_0001; // Set carry flag
+        } else {
+            self.status &= 0b1111_1110; // Clear carry flag
+        }
+        hi &= 0x0F;
+
+        ((hi << 4) | lo) as u8
+    }
+
+    /// Nibble-wise BCD subtraction of `a - operand - (1 - carry_in)`,
+    /// mirroring `decimal_add`'s nibble handling: subtracting a BCD digit is
+    /// the same as adding its nine's complement (each digit `d` maps to
+    /// `9 - d`, which never needs a borrow across nibbles since `d <= 9`),
+    /// so this subtracts `operand` by feeding `decimal_add` its nine's
+    /// complement -- the nibble that "went negative" is exactly the one
+    /// `decimal_add` corrects by adding 6.
+    #[cfg(feature = "decimal_mode")]
+    fn decimal_subtract(&mut self, a: u8, operand: u8, carry_in: u8) -> u8 {
+        let nines_complement = 0x99u8.wrapping_sub(operand);
+        self.decimal_add(a, nines_complement, carry_in)
+    }
+
+    /// Shared compare path for CMP/CPX/CPY: sets Carry if `reg >= operand`,
+    /// Zero if they're equal, and Negative from bit 7 of the difference.
+    fn compare(&mut self, reg: u8, operand: u8) {
+        let diff = reg.wrapping_sub(operand);
+
+        if reg >= operand {
+            self.status |= 0b0000_0001; // Set carry flag
+        } else {
+            self.status &= 0b1111_1110; // Clear carry flag
+        }
+
+        self.update_zero_and_negative_flags(diff);
+    }
+
+    /// Resolves the effective address for `mode`, advancing `program_counter`
+    /// past the operand bytes the mode consumes (zero for `Accumulator`/`Implied`).
+    /// The second element reports whether resolving the address crossed a
+    /// page boundary (high byte of the base differs from the effective
+    /// address), which costs an extra cycle on indexed reads.
     ///
-    /// The CPU reads instructions from memory via the Bus trait interface.
-    pub fn run(&mut self, bus: &mut impl Bus) {
-        loop {
-            // Handle interrupts before executing next instruction
-            if self.nmi_pending = false;{
-                self.nmi_pending = false;
-                self.handle_interrupt(bus, NMI_VECTOR);
-            } else if self.irq_pending = false; {
-                self.irq_pending = false;
-                self.handle_interrupt(bus, IRQ_VECTOR);
-            }
-
-            let opcode = bus.read(self.program_counter);
-
-            match opcode {
-                0xA9 => {
-                    // LDA Immediate: Load accumulator with immediate value
-                    let value = bus.read(self.program_counter.wrapping_add(1));
-                    self.program_counter = self.program_counter.wrapping_add(2);
-                    self.register_a = value;
-                    self.update_zero_and_negative_flags(self.register_a);
-                }
-                0xAD => {
-                    // LDA Absolute: Load accumulator from memory address
-                    let lo = bus.read(self.program_counter.wrapping_add(1)) as u16;
-                    let hi = bus.read(self.program_counter.wrapping_add(2)) as u16;
-                    let addr = (hi << 8) | lo;
-                    let value = bus.read(addr);
-                    self.program_counter = self.program_counter.wrapping_add(3);
-                    self.register_a = value;
-                    self.update_zero_and_negative_flags(self.register_a);
-                }
-                0xAA => {
-                    // TAX: Transfer accumulator to X register
-                    self.program_counter = self.program_counter.wrapping_add(1);
-                    self.register_x = self.register_a;
-                    self.update_zero_and_negative_flags(self.register_x);
-                }
-                0xE8 => {
-                    // INX: Increment X register
-                    self.program_counter = self.program_counter.wrapping_add(1);
-                    self.register_x = self.register_x.wrapping_add(1);
-                    self.update_zero_and_negative_flags(self.register_x);
-                }
-                0x8D => {
-                    // STA Absolute: Store accumulator to memory address
-                    let lo = bus.read(self.program_counter.wrapping_add(1)) as u16;
-                    let hi = bus.read(self.program_counter.wrapping_add(2)) as u16;
-                    let addr = (hi << 8) | lo;
-                    bus.write(addr, self.register_a);
-                    self.program_counter = self.program_counter.wrapping_add(3);
-                }
-                0x4C => {
-                    // JMP Absolute: Jump to new address
-                    let lo = bus.read(self.program_counter.wrapping_add(1)) as u16;
-                    let hi = bus.read(self.program_counter.wrapping_add(2)) as u16;
-                    self.program_counter = (hi << 8) | lo;
-                }
-                0xF0 => {
-                    // BEQ: Branch if equal (zero flag set)
-                    let offset = bus.read(self.program_counter.wrapping_add(1)) as i8;
-                    self.program_counter = self.program_counter.wrapping_add(2);
-                    if self.status & 0b0000_0010 != 0 {
-                        self.branch(offset);
-                    }
-                }
-                0xD0 => {
-                    // BNE: Branch if not equal (zero flag clear)
-                    let offset = bus.read(self.program_counter.wrapping_add(1)) as i8;
-                    self.program_counter = self.program_counter.wrapping_add(2);
-                    if self.status & 0b0000_0010 == 0 {
-                        self.branch(offset);
-                    }
-                }
-                0x90 => {
-                    // BCC: Branch if carry clear
-                    let offset = bus.read(self.program_counter.wrapping_add(1)) as i8;
-                    self.program_counter = self.program_counter.wrapping_add(2);
-                    if self.status & 0b0000_0001 == 0 {
-                        self.branch(offset);
-                    }
-                }
-                0xB0 => {
-                    // BCS: Branch if carry set
-                    let offset = bus.read(self.program_counter.wrapping_add(1)) as i8;
-                    self.program_counter = self.program_counter.wrapping_add(2);
-                    if self.status & 0b0000_0001 != 0 {
-                        self.branch(offset);
-                    }
+    /// For `Relative`, the "address" returned is already the branch target,
+    /// and the page-crossed flag compares the branch target against the
+    /// address immediately following the branch instruction.
+    /// For `Accumulator`/`Implied` both return values are unused by callers.
+    fn operand_address(&mut self, bus: &mut impl Bus, mode: AddressingMode) -> (u16, bool) {
+        match mode {
+            AddressingMode::Immediate => {
+                let addr = self.program_counter;
+                self.program_counter = self.program_counter.wrapping_add(1);
+                (addr, false)
+            }
+            AddressingMode::ZeroPage => {
+                let addr = bus.read(self.program_counter) as u16;
+                self.program_counter = self.program_counter.wrapping_add(1);
+                (addr, false)
+            }
+            AddressingMode::ZeroPageX => {
+                let base = bus.read(self.program_counter);
+                self.program_counter = self.program_counter.wrapping_add(1);
+                (base.wrapping_add(self.register_x) as u16, false)
+            }
+            AddressingMode::ZeroPageY => {
+                let base = bus.read(self.program_counter);
+                self.program_counter = self.program_counter.wrapping_add(1);
+                (base.wrapping_add(self.register_y) as u16, false)
+            }
+            AddressingMode::Absolute => {
+                let lo = bus.read(self.program_counter) as u16;
+                let hi = bus.read(self.program_counter.wrapping_add(1)) as u16;
+                self.program_counter = self.program_counter.wrapping_add(2);
+                ((hi << 8) | lo, false)
+            }
+            AddressingMode::AbsoluteX => {
+                let lo = bus.read(self.program_counter) as u16;
+                let hi = bus.read(self.program_counter.wrapping_add(1)) as u16;
+                self.program_counter = self.program_counter.wrapping_add(2);
+                let base = (hi << 8) | lo;
+                let addr = base.wrapping_add(self.register_x as u16);
+                (addr, (base & 0xFF00) != (addr & 0xFF00))
+            }
+            AddressingMode::AbsoluteY => {
+                let lo = bus.read(self.program_counter) as u16;
+                let hi = bus.read(self.program_counter.wrapping_add(1)) as u16;
+                self.program_counter = self.program_counter.wrapping_add(2);
+                let base = (hi << 8) | lo;
+                let addr = base.wrapping_add(self.register_y as u16);
+                (addr, (base & 0xFF00) != (addr & 0xFF00))
+            }
+            AddressingMode::IndirectX => {
+                let base = bus.read(self.program_counter);
+                self.program_counter = self.program_counter.wrapping_add(1);
+                let ptr = base.wrapping_add(self.register_x);
+                let lo = bus.read(ptr as u16) as u16;
+                let hi = bus.read(ptr.wrapping_add(1) as u16) as u16;
+                ((hi << 8) | lo, false)
+            }
+            AddressingMode::IndirectY => {
+                let ptr = bus.read(self.program_counter);
+                self.program_counter = self.program_counter.wrapping_add(1);
+                let lo = bus.read(ptr as u16) as u16;
+                let hi = bus.read(ptr.wrapping_add(1) as u16) as u16;
+                let base = (hi << 8) | lo;
+                let addr = base.wrapping_add(self.register_y as u16);
+                (addr, (base & 0xFF00) != (addr & 0xFF00))
+            }
+            AddressingMode::Indirect => {
+                let lo = bus.read(self.program_counter) as u16;
+                let hi = bus.read(self.program_counter.wrapping_add(1)) as u16;
+                self.program_counter = self.program_counter.wrapping_add(2);
+                let ptr = (hi << 8) | lo;
+
+                let target_lo = bus.read(ptr) as u16;
+                // NMOS famously fails to carry into the pointer's high byte
+                // when its low byte is 0xFF: JMP ($10FF) reads the target's
+                // high byte from $1000, not $1100. The 65C02 fixes this.
+                let hi_addr = if V::FIXES_INDIRECT_JMP_BUG {
+                    ptr.wrapping_add(1)
+                } else {
+                    (ptr & 0xFF00) | (ptr.wrapping_add(1) & 0x00FF)
+                };
+                let target_hi = bus.read(hi_addr) as u16;
+                ((target_hi << 8) | target_lo, false)
+            }
+            AddressingMode::Relative => {
+                let offset = bus.read(self.program_counter) as i8;
+                self.program_counter = self.program_counter.wrapping_add(1);
+                let next = self.program_counter;
+                let target = (next as i32 + offset as i32) as u16;
+                (target, (next & 0xFF00) != (target & 0xFF00))
+            }
+            AddressingMode::Accumulator | AddressingMode::Implied => (0, false),
+        }
+    }
+
+    /// Whether a page-crossing read in this addressing mode costs an extra
+    /// cycle. Only indexed reads (not zero-page/absolute/writes) are penalized.
+    fn pays_page_cross_penalty(mode: AddressingMode) -> bool {
+        matches!(
+            mode,
+            AddressingMode::AbsoluteX | AddressingMode::AbsoluteY | AddressingMode::IndirectY
+        )
+    }
+
+    /// Executes a single decoded instruction, reading/writing its operand
+    /// through the address resolved by `operand_address`. Returns the
+    /// number of cycles to add on top of the opcode's base cycle count
+    /// (page-cross and branch-taken penalties).
+    fn execute(&mut self, bus: &mut impl Bus, instruction: Instruction, mode: AddressingMode) -> u8 {
+        match instruction {
+            Instruction::Lda => {
+                let (addr, crossed) = self.operand_address(bus, mode);
+                self.register_a = bus.read(addr);
+                self.update_zero_and_negative_flags(self.register_a);
+                (crossed && Self::pays_page_cross_penalty(mode)) as u8
+            }
+            Instruction::Sta => {
+                let (addr, _) = self.operand_address(bus, mode);
+                bus.write(addr, self.register_a);
+                0
+            }
+            Instruction::Tax => {
+                self.register_x = self.register_a;
+                self.update_zero_and_negative_flags(self.register_x);
+                0
+            }
+            Instruction::Inx => {
+                self.register_x = self.register_x.wrapping_add(1);
+                self.update_zero_and_negative_flags(self.register_x);
+                0
+            }
+            Instruction::Jmp => {
+                let (addr, _) = self.operand_address(bus, mode);
+                self.program_counter = addr;
+                0
+            }
+            Instruction::Beq => self.branch(bus, mode, self.status & 0b0000_0010 != 0),
+            Instruction::Bne => self.branch(bus, mode, self.status & 0b0000_0010 == 0),
+            Instruction::Bcc => self.branch(bus, mode, self.status & 0b0000_0001 == 0),
+            Instruction::Bcs => self.branch(bus, mode, self.status & 0b0000_0001 != 0),
+            Instruction::Bmi => self.branch(bus, mode, self.status & 0b1000_0000 != 0),
+            Instruction::Bpl => self.branch(bus, mode, self.status & 0b1000_0000 == 0),
+            Instruction::Brk => {
+                self.push_word(bus, self.program_counter);
+                self.push_byte(bus, self.status | 0b0001_0000);
+                self.status |= 0b0000_0100;
+                if V::CLEARS_DECIMAL_ON_BRK {
+                    self.status &= 0b1111_0111; // Clear decimal flag (CMOS only)
                 }
-                0x30 => {
-                    // BMI: Branch if negative set
-                    let offset = bus.read(self.program_counter.wrapping_add(1)) as i8;
-                    self.program_counter = self.program_counter.wrapping_add(2);
-                    if self.status & 0b1000_0000 != 0 {
-                        self.branch(offset);
-                    }
+                let lo = bus.read(IRQ_VECTOR) as u16;
+                let hi = bus.read(IRQ_VECTOR + 1) as u16;
+                self.program_counter = (hi << 8) | lo;
+                0
+            }
+            Instruction::Pha => {
+                self.push_byte(bus, self.register_a);
+                0
+            }
+            Instruction::Pla => {
+                self.register_a = self.pop_byte(bus);
+                self.update_zero_and_negative_flags(self.register_a);
+                0
+            }
+            Instruction::Php => {
+                self.push_byte(bus, self.status | 0b0011_0000);
+                0
+            }
+            Instruction::Plp => {
+                self.status = self.pop_byte(bus);
+                0
+            }
+            Instruction::Jsr => {
+                let (addr, _) = self.operand_address(bus, mode);
+                self.push_word(bus, self.program_counter.wrapping_sub(1));
+                self.program_counter = addr;
+                0
+            }
+            Instruction::Rts => {
+                self.program_counter = self.pop_word(bus).wrapping_add(1);
+                0
+            }
+            Instruction::Rti => {
+                self.status = self.pop_byte(bus);
+                self.program_counter = self.pop_word(bus);
+                0
+            }
+            Instruction::Adc => {
+                let (addr, crossed) = self.operand_address(bus, mode);
+                let operand = bus.read(addr);
+                self.adc(operand);
+                (crossed && Self::pays_page_cross_penalty(mode)) as u8
+            }
+            Instruction::Sbc => {
+                let (addr, crossed) = self.operand_address(bus, mode);
+                let operand = bus.read(addr);
+                self.sbc(operand);
+                (crossed && Self::pays_page_cross_penalty(mode)) as u8
+            }
+            Instruction::And => {
+                let (addr, crossed) = self.operand_address(bus, mode);
+                self.register_a &= bus.read(addr);
+                self.update_zero_and_negative_flags(self.register_a);
+                (crossed && Self::pays_page_cross_penalty(mode)) as u8
+            }
+            Instruction::Ora => {
+                let (addr, crossed) = self.operand_address(bus, mode);
+                self.register_a |= bus.read(addr);
+                self.update_zero_and_negative_flags(self.register_a);
+                (crossed && Self::pays_page_cross_penalty(mode)) as u8
+            }
+            Instruction::Eor => {
+                let (addr, crossed) = self.operand_address(bus, mode);
+                self.register_a ^= bus.read(addr);
+                self.update_zero_and_negative_flags(self.register_a);
+                (crossed && Self::pays_page_cross_penalty(mode)) as u8
+            }
+            Instruction::Cmp => {
+                let (addr, crossed) = self.operand_address(bus, mode);
+                let operand = bus.read(addr);
+                self.compare(self.register_a, operand);
+                (crossed && Self::pays_page_cross_penalty(mode)) as u8
+            }
+            Instruction::Cpx => {
+                let (addr, _) = self.operand_address(bus, mode);
+                let operand = bus.read(addr);
+                self.compare(self.register_x, operand);
+                0
+            }
+            Instruction::Cpy => {
+                let (addr, _) = self.operand_address(bus, mode);
+                let operand = bus.read(addr);
+                self.compare(self.register_y, operand);
+                0
+            }
+            Instruction::Bit => {
+                let (addr, _) = self.operand_address(bus, mode);
+                let operand = bus.read(addr);
+
+                if self.register_a & operand == 0 {
+                    self.status |= 0b0000_0010; // Set zero flag
+                } else {
+                    self.status &= 0b1111_1101; // Clear zero flag
                 }
-                0x10 => {
-                    // BPL: Branch if negative clear
-                    let offset = bus.read(self.program_counter.wrapping_add(1)) as i8;
-                    self.program_counter = self.program_counter.wrapping_add(2);
-                    if self.status & 0b1000_0000 == 0 {
-                        self.branch(offset);
-                    }
+
+                // Immediate-mode BIT (65C02) only affects Z; there's no
+                // memory location for N/V to be "observed" from.
+                if mode != AddressingMode::Immediate {
+                    // N and V come directly from bits 7 and 6 of the operand.
+                    self.status = (self.status & 0b0011_1111) | (operand & 0b1100_0000);
                 }
-                0x00 => {
-                    // BRK: Force interrupt
-                    self.program_counter = self.program_counter.wrapping_add(1);
+                0
+            }
+            Instruction::Stz => {
+                let (addr, _) = self.operand_address(bus, mode);
+                bus.write(addr, 0);
+                0
+            }
+            Instruction::Trb => {
+                let (addr, _) = self.operand_address(bus, mode);
+                let operand = bus.read(addr);
 
-                    // Push PC and status (break flag set)
-                    self.push_word(bus, self.program_counter);
-                    self.push_byte(bus, self.status | 0b0001_0000);
+                if self.register_a & operand == 0 {
+                    self.status |= 0b0000_0010; // Set zero flag
+                } else {
+                    self.status &= 0b1111_1101; // Clear zero flag
+                }
 
-                    // Set interrupt disable
-                    self.status |= 0b0000_0100;
+                bus.write(addr, operand & !self.register_a);
+                0
+            }
+            Instruction::Tsb => {
+                let (addr, _) = self.operand_address(bus, mode);
+                let operand = bus.read(addr);
 
-                    // Jump to IRQ/BRK vector
-                    let lo = bus.read(IRQ_VECTOR) as u16;
-                    let hi = bus.read(IRQ_VECTOR + 1) as u16;
-                    self.program_counter = (hi << 8) | lo;
-                }
-                0x48 => {
-                // PHA: Push accumulator to stack
-                    self.push_byte(bus, self.register_a);
-                    self.program_counter = self.program_counter.wrapping_add(1);
-                },
-                0x68 => {
-                    // PLA: Pull accumulator from stack
-                    self.register_a = self.pop_byte(bus);
-                    self.update_zero_and_negative_flags(self.register_a);
-                    self.program_counter = self.program_counter.wrapping_add(1);
-                },
-                0x08 => {
-                    // PHP: Push processor status to stack (set B flag + unused)
-                    self.push_byte(bus, self.status | 0b0011_0000);
-                    self.program_counter = self.program_counter.wrapping_add(1);
-                },
-                0x28 => {
-                    // PLP: Pull processor status from stack
-                    self.status = self.pop_byte(bus);
-                    self.program_counter = self.program_counter.wrapping_add(1);
-                },
-                0x20 => {
-                    // JSR Absolute: Jump to subroutine
-                    let lo = bus.read(self.program_counter.wrapping_add(1)) as u16;
-                    let hi = bus.read(self.program_counter.wrapping_add(2)) as u16;
-                    let addr = (hi << 8) | lo;
-                    // Push return address (PC + 2) onto stack
-                    self.push_word(bus, self.program_counter.wrapping_add(2));
-                    self.program_counter = addr;
-                },
-                0x60 => {
-                    // RTS: Return from subroutine
-                    self.program_counter = self.pop_word(bus).wrapping_add(1);
-                },
-                0x40 => {
-                    // RTI: Return from interrupt
-                    self.status = self.pop_byte(bus);          // Restore status flags
-                    self.program_counter = self.pop_word(bus); // Restore PC
+                if self.register_a & operand == 0 {
+                    self.status |= 0b0000_0010; // Set zero flag
+                } else {
+                    self.status &= 0b1111_1101; // Clear zero flag
                 }
-                _ => panic!("Opcode {:#x} not implemented", opcode),
+
+                bus.write(addr, operand | self.register_a);
+                0
+            }
+            Instruction::Bra => {
+                let (addr, crossed) = self.operand_address(bus, mode);
+                self.program_counter = addr;
+                crossed as u8
             }
+            Instruction::Phx => {
+                self.push_byte(bus, self.register_x);
+                0
+            }
+            Instruction::Phy => {
+                self.push_byte(bus, self.register_y);
+                0
+            }
+            Instruction::Plx => {
+                self.register_x = self.pop_byte(bus);
+                self.update_zero_and_negative_flags(self.register_x);
+                0
+            }
+            Instruction::Ply => {
+                self.register_y = self.pop_byte(bus);
+                self.update_zero_and_negative_flags(self.register_y);
+                0
+            }
+            Instruction::IncA => {
+                self.register_a = self.register_a.wrapping_add(1);
+                self.update_zero_and_negative_flags(self.register_a);
+                0
+            }
+            Instruction::DecA => {
+                self.register_a = self.register_a.wrapping_sub(1);
+                self.update_zero_and_negative_flags(self.register_a);
+                0
+            }
+        }
+    }
+
+    /// Shared path for the six conditional branches: resolves the relative
+    /// target, and if `taken`, jumps and charges +1 cycle (+1 more if the
+    /// target lands on a different page than the following instruction).
+    fn branch(&mut self, bus: &mut impl Bus, mode: AddressingMode, taken: bool) -> u8 {
+        let (target, crossed) = self.operand_address(bus, mode);
+        if !taken {
+            return 0;
         }
+        self.program_counter = target;
+        1 + crossed as u8
+    }
+
+    /// Executes exactly one instruction, or -- if NMI/IRQ is pending and
+    /// taken -- exactly one interrupt dispatch instead, and returns the
+    /// number of cycles it cost. A dispatch and the first instruction at
+    /// its vector are always two separate `step` calls, never fused into
+    /// one. `run` is a thin wrapper that calls this in a loop.
+    pub fn step(&mut self, bus: &mut impl Bus) -> u8 {
+        // Handle interrupts before executing next instruction. NMI is
+        // edge-triggered: `trigger_nmi` latches it and it fires exactly
+        // once here. IRQ is level-triggered: it stays pending (and keeps
+        // being re-checked) while the I flag masks it, and is cleared only
+        // once it's taken; requesting another needs another `trigger_irq`.
+        if self.nmi_pending {
+            self.nmi_pending = false;
+            self.handle_interrupt(bus, NMI_VECTOR);
+            self.cycles = self.cycles.wrapping_add(INTERRUPT_DISPATCH_CYCLES as u64);
+            return INTERRUPT_DISPATCH_CYCLES;
+        } else if self.irq_pending && self.status & 0b0000_0100 == 0 {
+            self.irq_pending = false;
+            self.handle_interrupt(bus, IRQ_VECTOR);
+            self.cycles = self.cycles.wrapping_add(INTERRUPT_DISPATCH_CYCLES as u64);
+            return INTERRUPT_DISPATCH_CYCLES;
+        }
+
+        let opcode = bus.read(self.program_counter);
+        self.program_counter = self.program_counter.wrapping_add(1);
+
+        let (instruction, mode, base_cycles) = V::decode(opcode)
+            .unwrap_or_else(|| panic!("Opcode {:#x} not implemented", opcode));
+        let extra_cycles = self.execute(bus, instruction, mode);
+
+        let total = base_cycles + extra_cycles;
+        self.cycles = self.cycles.wrapping_add(total as u64);
+        total
+    }
+
+    /// Runs the CPU by calling `step` in a loop. Never returns: BRK
+    /// dispatches through the IRQ vector as on real hardware instead of
+    /// halting execution.
+    pub fn run(&mut self, bus: &mut impl Bus) {
+        loop {
+            self.step(bus);
+        }
+    }
+}
+
+impl<V: Variant> Savable for CPU<V> {
+    /// Serializes every register, the status byte, PC, stack pointer,
+    /// cycle count, and pending-interrupt flags. `V` carries no runtime
+    /// state (it's a zero-sized marker), so it isn't part of the snapshot.
+    fn save_state(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(17);
+        out.push(self.register_a);
+        out.push(self.register_x);
+        out.push(self.register_y);
+        out.push(self.status);
+        out.extend_from_slice(&self.program_counter.to_le_bytes());
+        out.push(self.stack_pointer);
+        out.extend_from_slice(&self.cycles.to_le_bytes());
+        out.push(self.nmi_pending as u8);
+        out.push(self.irq_pending as u8);
+        out
+    }
+
+    fn load_state(&mut self, data: &[u8]) {
+        self.register_a = data[0];
+        self.register_x = data[1];
+        self.register_y = data[2];
+        self.status = data[3];
+        self.program_counter = u16::from_le_bytes([data[4], data[5]]);
+        self.stack_pointer = data[6];
+        self.cycles = u64::from_le_bytes(data[7..15].try_into().unwrap());
+        self.nmi_pending = data[15] != 0;
+        self.irq_pending = data[16] != 0;
     }
 }
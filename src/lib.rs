@@ -0,0 +1,14 @@
+// Crate name mirrors the repo name (NES6502-Emulator); Cargo maps the
+// hyphen to `_`, landing the library identifier outside snake_case.
+#![allow(non_snake_case)]
+
+pub mod bus;
+pub mod cpu;
+pub mod opcodes;
+pub mod savestate;
+pub mod variant;
+
+pub use bus::{Bus, Ram};
+pub use cpu::CPU;
+pub use savestate::Savable;
+pub use variant::{Cmos, Nmos, Variant};
@@ -5,6 +5,12 @@ pub struct CPU{
     pub program_counter: u16
 }
 
+impl Default for CPU {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl CPU{
     pub fn new() -> Self{
         CPU {
@@ -17,15 +23,15 @@ impl CPU{
 
     fn update_zero_and_negative_flags(&mut self, result: u8) {
         if result == 0 {
-            self.status = self.status | 0b0000_0010;
+            self.status |= 0b0000_0010;
         } else {
-            self.status = self.status & 0b1111_1101;
+            self.status &= 0b1111_1101;
         }
 
         if result & 0b1000_0000 != 0 {
-            self.status = self.status | 0b1000_0000;
+            self.status |= 0b1000_0000;
         } else {
-            self.status = self.status & 0b0111_1111;
+            self.status &= 0b0111_1111;
         }
     }
 
@@ -70,15 +76,21 @@ impl CPU{
     }
 }
 
+fn main() {
+    let mut cpu = CPU::new();
+    cpu.interpret(vec![0xA9, 0x10, 0x00]);
+    println!("Register A: {}", cpu.register_a);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-   
+
     #[test]
     fn test_5_ops_working_together() {
        let mut cpu = CPU::new();
        cpu.interpret(vec![0xa9, 0xc0, 0xaa, 0xe8, 0x00]);
- 
+
        assert_eq!(cpu.register_x, 0xc1)
     }
 
@@ -91,9 +103,3 @@ mod tests {
         assert_eq!(cpu.register_x, 1)
     }
 }
-
-fn main() {
-    let mut cpu = CPU::new();
-    cpu.interpret(vec![0xA9, 0x10, 0x00]);
-    println!("Register A: {}", cpu.register_a);
-}
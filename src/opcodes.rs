@@ -0,0 +1,208 @@
+/// Addressing modes supported by the 6502 family.
+///
+/// Each opcode in `OPCODE_TABLE` is paired with one of these, and
+/// `CPU::operand_address` knows how to resolve each variant into an
+/// effective address and how many operand bytes it consumes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressingMode {
+    Immediate,
+    ZeroPage,
+    ZeroPageX,
+    ZeroPageY,
+    Absolute,
+    AbsoluteX,
+    AbsoluteY,
+    IndirectX,
+    IndirectY,
+    Relative,
+    Accumulator,
+    Implied,
+    Indirect,
+}
+
+/// The operation an opcode performs, independent of how its operand is
+/// addressed. Combined with an `AddressingMode` this fully describes an
+/// opcode; see `OPCODE_TABLE`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    Lda,
+    Sta,
+    Tax,
+    Inx,
+    Jmp,
+    Beq,
+    Bne,
+    Bcc,
+    Bcs,
+    Bmi,
+    Bpl,
+    Brk,
+    Pha,
+    Pla,
+    Php,
+    Plp,
+    Jsr,
+    Rts,
+    Rti,
+    Adc,
+    Sbc,
+    And,
+    Ora,
+    Eor,
+    Cmp,
+    Cpx,
+    Cpy,
+    Bit,
+    Stz,
+    Trb,
+    Tsb,
+    Bra,
+    Phx,
+    Phy,
+    Plx,
+    Ply,
+    IncA,
+    DecA,
+}
+
+/// A decoded opcode: what it does, how its operand is addressed, and the
+/// base cycle count it costs before any page-cross/branch penalties.
+pub type Opcode = (Instruction, AddressingMode, u8);
+
+/// Maps every opcode byte to its `Opcode`. Opcodes with no entry (`None`)
+/// are not yet implemented.
+pub const OPCODE_TABLE: [Option<Opcode>; 256] = build_table();
+
+const fn build_table() -> [Option<Opcode>; 256] {
+    let mut table: [Option<Opcode>; 256] = [None; 256];
+
+    table[0xA9] = Some((Instruction::Lda, AddressingMode::Immediate, 2));
+    table[0xAD] = Some((Instruction::Lda, AddressingMode::Absolute, 4));
+    table[0x8D] = Some((Instruction::Sta, AddressingMode::Absolute, 4));
+    table[0xAA] = Some((Instruction::Tax, AddressingMode::Implied, 2));
+    table[0xE8] = Some((Instruction::Inx, AddressingMode::Implied, 2));
+    table[0x4C] = Some((Instruction::Jmp, AddressingMode::Absolute, 3));
+    table[0x6C] = Some((Instruction::Jmp, AddressingMode::Indirect, 5));
+    table[0xF0] = Some((Instruction::Beq, AddressingMode::Relative, 2));
+    table[0xD0] = Some((Instruction::Bne, AddressingMode::Relative, 2));
+    table[0x90] = Some((Instruction::Bcc, AddressingMode::Relative, 2));
+    table[0xB0] = Some((Instruction::Bcs, AddressingMode::Relative, 2));
+    table[0x30] = Some((Instruction::Bmi, AddressingMode::Relative, 2));
+    table[0x10] = Some((Instruction::Bpl, AddressingMode::Relative, 2));
+    table[0x00] = Some((Instruction::Brk, AddressingMode::Implied, 7));
+    table[0x48] = Some((Instruction::Pha, AddressingMode::Implied, 3));
+    table[0x68] = Some((Instruction::Pla, AddressingMode::Implied, 4));
+    table[0x08] = Some((Instruction::Php, AddressingMode::Implied, 3));
+    table[0x28] = Some((Instruction::Plp, AddressingMode::Implied, 4));
+    table[0x20] = Some((Instruction::Jsr, AddressingMode::Absolute, 6));
+    table[0x60] = Some((Instruction::Rts, AddressingMode::Implied, 6));
+    table[0x40] = Some((Instruction::Rti, AddressingMode::Implied, 6));
+
+    table[0x69] = Some((Instruction::Adc, AddressingMode::Immediate, 2));
+    table[0x65] = Some((Instruction::Adc, AddressingMode::ZeroPage, 3));
+    table[0x75] = Some((Instruction::Adc, AddressingMode::ZeroPageX, 4));
+    table[0x6D] = Some((Instruction::Adc, AddressingMode::Absolute, 4));
+    table[0x7D] = Some((Instruction::Adc, AddressingMode::AbsoluteX, 4));
+    table[0x79] = Some((Instruction::Adc, AddressingMode::AbsoluteY, 4));
+    table[0x61] = Some((Instruction::Adc, AddressingMode::IndirectX, 6));
+    table[0x71] = Some((Instruction::Adc, AddressingMode::IndirectY, 5));
+
+    table[0xE9] = Some((Instruction::Sbc, AddressingMode::Immediate, 2));
+    table[0xE5] = Some((Instruction::Sbc, AddressingMode::ZeroPage, 3));
+    table[0xF5] = Some((Instruction::Sbc, AddressingMode::ZeroPageX, 4));
+    table[0xED] = Some((Instruction::Sbc, AddressingMode::Absolute, 4));
+    table[0xFD] = Some((Instruction::Sbc, AddressingMode::AbsoluteX, 4));
+    table[0xF9] = Some((Instruction::Sbc, AddressingMode::AbsoluteY, 4));
+    table[0xE1] = Some((Instruction::Sbc, AddressingMode::IndirectX, 6));
+    table[0xF1] = Some((Instruction::Sbc, AddressingMode::IndirectY, 5));
+
+    table[0x29] = Some((Instruction::And, AddressingMode::Immediate, 2));
+    table[0x25] = Some((Instruction::And, AddressingMode::ZeroPage, 3));
+    table[0x35] = Some((Instruction::And, AddressingMode::ZeroPageX, 4));
+    table[0x2D] = Some((Instruction::And, AddressingMode::Absolute, 4));
+    table[0x3D] = Some((Instruction::And, AddressingMode::AbsoluteX, 4));
+    table[0x39] = Some((Instruction::And, AddressingMode::AbsoluteY, 4));
+    table[0x21] = Some((Instruction::And, AddressingMode::IndirectX, 6));
+    table[0x31] = Some((Instruction::And, AddressingMode::IndirectY, 5));
+
+    table[0x09] = Some((Instruction::Ora, AddressingMode::Immediate, 2));
+    table[0x05] = Some((Instruction::Ora, AddressingMode::ZeroPage, 3));
+    table[0x15] = Some((Instruction::Ora, AddressingMode::ZeroPageX, 4));
+    table[0x0D] = Some((Instruction::Ora, AddressingMode::Absolute, 4));
+    table[0x1D] = Some((Instruction::Ora, AddressingMode::AbsoluteX, 4));
+    table[0x19] = Some((Instruction::Ora, AddressingMode::AbsoluteY, 4));
+    table[0x01] = Some((Instruction::Ora, AddressingMode::IndirectX, 6));
+    table[0x11] = Some((Instruction::Ora, AddressingMode::IndirectY, 5));
+
+    table[0x49] = Some((Instruction::Eor, AddressingMode::Immediate, 2));
+    table[0x45] = Some((Instruction::Eor, AddressingMode::ZeroPage, 3));
+    table[0x55] = Some((Instruction::Eor, AddressingMode::ZeroPageX, 4));
+    table[0x4D] = Some((Instruction::Eor, AddressingMode::Absolute, 4));
+    table[0x5D] = Some((Instruction::Eor, AddressingMode::AbsoluteX, 4));
+    table[0x59] = Some((Instruction::Eor, AddressingMode::AbsoluteY, 4));
+    table[0x41] = Some((Instruction::Eor, AddressingMode::IndirectX, 6));
+    table[0x51] = Some((Instruction::Eor, AddressingMode::IndirectY, 5));
+
+    table[0xC9] = Some((Instruction::Cmp, AddressingMode::Immediate, 2));
+    table[0xC5] = Some((Instruction::Cmp, AddressingMode::ZeroPage, 3));
+    table[0xD5] = Some((Instruction::Cmp, AddressingMode::ZeroPageX, 4));
+    table[0xCD] = Some((Instruction::Cmp, AddressingMode::Absolute, 4));
+    table[0xDD] = Some((Instruction::Cmp, AddressingMode::AbsoluteX, 4));
+    table[0xD9] = Some((Instruction::Cmp, AddressingMode::AbsoluteY, 4));
+    table[0xC1] = Some((Instruction::Cmp, AddressingMode::IndirectX, 6));
+    table[0xD1] = Some((Instruction::Cmp, AddressingMode::IndirectY, 5));
+
+    table[0xE0] = Some((Instruction::Cpx, AddressingMode::Immediate, 2));
+    table[0xE4] = Some((Instruction::Cpx, AddressingMode::ZeroPage, 3));
+    table[0xEC] = Some((Instruction::Cpx, AddressingMode::Absolute, 4));
+
+    table[0xC0] = Some((Instruction::Cpy, AddressingMode::Immediate, 2));
+    table[0xC4] = Some((Instruction::Cpy, AddressingMode::ZeroPage, 3));
+    table[0xCC] = Some((Instruction::Cpy, AddressingMode::Absolute, 4));
+
+    table[0x24] = Some((Instruction::Bit, AddressingMode::ZeroPage, 3));
+    table[0x2C] = Some((Instruction::Bit, AddressingMode::Absolute, 4));
+
+    table
+}
+
+/// Opcodes added by the CMOS 65C02 on top of `OPCODE_TABLE`. Looked up
+/// first by `Cmos::decode`, falling back to the NMOS table for everything
+/// the two variants share.
+pub const CMOS_OPCODE_TABLE: [Option<Opcode>; 256] = build_cmos_table();
+
+const fn build_cmos_table() -> [Option<Opcode>; 256] {
+    let mut table: [Option<Opcode>; 256] = [None; 256];
+
+    table[0x64] = Some((Instruction::Stz, AddressingMode::ZeroPage, 3));
+    table[0x74] = Some((Instruction::Stz, AddressingMode::ZeroPageX, 4));
+    table[0x9C] = Some((Instruction::Stz, AddressingMode::Absolute, 4));
+    table[0x9E] = Some((Instruction::Stz, AddressingMode::AbsoluteX, 5));
+
+    table[0x14] = Some((Instruction::Trb, AddressingMode::ZeroPage, 5));
+    table[0x1C] = Some((Instruction::Trb, AddressingMode::Absolute, 6));
+
+    table[0x04] = Some((Instruction::Tsb, AddressingMode::ZeroPage, 5));
+    table[0x0C] = Some((Instruction::Tsb, AddressingMode::Absolute, 6));
+
+    // BRA is always taken, so its base cost already includes the +1 that
+    // the conditional branches only pay when taken.
+    table[0x80] = Some((Instruction::Bra, AddressingMode::Relative, 3));
+
+    table[0xDA] = Some((Instruction::Phx, AddressingMode::Implied, 3));
+    table[0x5A] = Some((Instruction::Phy, AddressingMode::Implied, 3));
+    table[0xFA] = Some((Instruction::Plx, AddressingMode::Implied, 4));
+    table[0x7A] = Some((Instruction::Ply, AddressingMode::Implied, 4));
+
+    table[0x1A] = Some((Instruction::IncA, AddressingMode::Accumulator, 2));
+    table[0x3A] = Some((Instruction::DecA, AddressingMode::Accumulator, 2));
+
+    table[0x89] = Some((Instruction::Bit, AddressingMode::Immediate, 2));
+
+    // Same opcode as NMOS, but CPU::operand_address gates the indirect
+    // fetch on `Variant::FIXES_INDIRECT_JMP_BUG`, and the 65C02 takes one
+    // extra cycle to do the carry-propagating fetch correctly.
+    table[0x6C] = Some((Instruction::Jmp, AddressingMode::Indirect, 6));
+
+    table
+}
@@ -0,0 +1,11 @@
+/// Types that can snapshot their full internal state to a byte buffer and
+/// restore from one. This is the foundation for save states and, later,
+/// a rewind buffer: callers pair a `CPU`'s snapshot with its `Bus`'s to
+/// capture a complete machine state.
+pub trait Savable {
+    /// Serializes the current state into a new byte buffer.
+    fn save_state(&self) -> Vec<u8>;
+
+    /// Restores state from a buffer previously produced by `save_state`.
+    fn load_state(&mut self, data: &[u8]);
+}
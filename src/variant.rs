@@ -0,0 +1,45 @@
+use crate::opcodes::{Opcode, CMOS_OPCODE_TABLE, OPCODE_TABLE};
+
+/// Distinguishes the original NMOS 6502 core from its CMOS 65C02
+/// successor so a single `CPU<V>` can emulate either one. Implementors
+/// gate opcode decoding and the handful of behavioral differences
+/// between the two (e.g. whether `BRK` clears the Decimal flag).
+pub trait Variant {
+    /// Whether `BRK` clears the Decimal flag (true on 65C02, false on NMOS).
+    const CLEARS_DECIMAL_ON_BRK: bool;
+
+    /// Whether indirect `JMP ($addr)` correctly carries into the pointer's
+    /// high byte when its low byte is 0xFF (true on 65C02; false on NMOS,
+    /// which has the famous page-wrap bug).
+    const FIXES_INDIRECT_JMP_BUG: bool;
+
+    /// Decodes `opcode` into its instruction, addressing mode, and base
+    /// cycle count for this variant.
+    fn decode(opcode: u8) -> Option<Opcode>;
+}
+
+/// The original NMOS 6502, as used (in the guise of the Ricoh 2A03) in
+/// the NES. Keeps the NMOS instruction set and its hardware quirks.
+pub struct Nmos;
+
+impl Variant for Nmos {
+    const CLEARS_DECIMAL_ON_BRK: bool = false;
+    const FIXES_INDIRECT_JMP_BUG: bool = false;
+
+    fn decode(opcode: u8) -> Option<Opcode> {
+        OPCODE_TABLE[opcode as usize]
+    }
+}
+
+/// The CMOS 65C02, which adds `STZ`/`TRB`/`TSB`/`BRA`/`PHX`/`PHY`/`PLX`/`PLY`,
+/// accumulator `INC`/`DEC`, immediate-mode `BIT`, and fixes several NMOS bugs.
+pub struct Cmos;
+
+impl Variant for Cmos {
+    const CLEARS_DECIMAL_ON_BRK: bool = true;
+    const FIXES_INDIRECT_JMP_BUG: bool = true;
+
+    fn decode(opcode: u8) -> Option<Opcode> {
+        CMOS_OPCODE_TABLE[opcode as usize].or_else(|| OPCODE_TABLE[opcode as usize])
+    }
+}
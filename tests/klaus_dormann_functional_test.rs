@@ -0,0 +1,60 @@
+use NES6502_emulator::{Ram, CPU};
+
+/// Start address (entry point) of the standard `6502_functional_test` build.
+const FUNCTIONAL_TEST_START: u16 = 0x0400;
+
+/// Generous ceiling so a regression that breaks the success trap fails
+/// fast instead of hanging the test suite.
+const MAX_INSTRUCTIONS: u64 = 100_000_000;
+
+/// Loads `program` (the functional-test image, assembled to run from its
+/// 0x0000 origin) into RAM, sets PC to the test's start address, and
+/// steps the CPU until it traps into an infinite loop (PC unchanged
+/// across a `step`) or the instruction budget runs out. Returns the
+/// address it trapped at, so the caller can compare it against the
+/// known success address.
+fn run_functional_test(program: &[u8]) -> u16 {
+    let mut ram = Ram::new();
+    ram.load(0x0000, program);
+
+    let mut cpu = CPU::new();
+    cpu.program_counter = FUNCTIONAL_TEST_START;
+
+    for _ in 0..MAX_INSTRUCTIONS {
+        let pc_before = cpu.program_counter;
+        cpu.step(&mut ram);
+        if cpu.program_counter == pc_before {
+            eprintln!(
+                "trapped at PC={:#06x}, A={:#04x}, X={:#04x}, status={:#04x}",
+                pc_before, cpu.register_a, cpu.register_x, cpu.status
+            );
+            return pc_before;
+        }
+    }
+
+    panic!(
+        "functional test did not trap within {} instructions (PC={:#06x}, A={:#04x}, X={:#04x}, status={:#04x})",
+        MAX_INSTRUCTIONS, cpu.program_counter, cpu.register_a, cpu.register_x, cpu.status
+    );
+}
+
+#[test]
+#[ignore = "requires the 6502_functional_test.bin fixture, not checked into this repo \
+            (download from https://github.com/Klaus2m5/6502_65C02_functional_tests and \
+            place it at tests/roms/6502_functional_test.bin)"]
+fn klaus_dormann_functional_test_passes() {
+    // Self-jump address the standard build traps at on success.
+    const SUCCESS_ADDRESS: u16 = 0x3469;
+
+    let program = std::fs::read("tests/roms/6502_functional_test.bin")
+        .expect("missing 6502_functional_test.bin fixture");
+
+    let trapped_at = run_functional_test(&program);
+
+    assert_eq!(
+        trapped_at, SUCCESS_ADDRESS,
+        "test ROM trapped at {:#06x} instead of the success address {:#06x} -- \
+         check the dump above for which sub-test failed",
+        trapped_at, SUCCESS_ADDRESS
+    );
+}